@@ -0,0 +1,304 @@
+use std::io::{Cursor, Read, Write};
+
+use binrw::io::NoSeek;
+use binrw::{binrw, BinRead, BinWrite};
+
+/// Section tag this tool writes into a section's header and footer u32 when
+/// producing a BMF file via [`write_bmf`].
+///
+/// These specific byte values are this crate's own convention, not a
+/// confirmed requirement of the wire format: we have no sample files or
+/// authoritative spec on hand to verify what real-world BMF producers put
+/// here, so [`load_bmf`] does not assert an incoming header/footer against
+/// these constants (see the `assert`s below, which only check that a
+/// section's header and footer agree with *each other*). Tightening that to
+/// a hard match against a specific tag should wait until these values are
+/// confirmed against real files.
+const BMF_TAG: u32 = u32::from_le_bytes(*b"BMF\0");
+const VERTICES_TAG: u32 = u32::from_le_bytes(*b"VERT");
+const GROUP_TAG: u32 = u32::from_le_bytes(*b"GRP\0");
+const FACES_TAG: u32 = u32::from_le_bytes(*b"FACE");
+const NORMALS_TAG: u32 = u32::from_le_bytes(*b"NORM");
+
+/// Sanity ceiling on a section's declared element count: each vertex/face/normal
+/// is 12 bytes on disk, so this bounds a single section to a few GB even on a
+/// corrupt/adversarial `len`, without needing a seekable stream to check the
+/// remaining length up front.
+const MAX_SECTION_LEN: u32 = 100_000_000;
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, Copy, Clone, Default, serde::Serialize)]
+pub struct Vertex {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, Copy, Clone, Default, serde::Serialize)]
+pub struct Face {
+    pub a: u32,
+    pub b: u32,
+    pub c: u32,
+}
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, serde::Serialize)]
+pub struct Vertices {
+    pub header: u32,
+    #[br(assert(len <= MAX_SECTION_LEN, "vertices section: absurd len {len} (max {MAX_SECTION_LEN})"))]
+    pub len: u32,
+    #[br(count = len as usize)]
+    pub vertices: Vec<Vertex>,
+    #[br(assert(footer == header, "vertices section: header/footer mismatch (header {header:#010x}, footer {footer:#010x})"))]
+    pub footer: u32,
+}
+
+impl Vertices {
+    pub fn new() -> Vertices {
+        Vertices { header: VERTICES_TAG, len: 0, vertices: Vec::new(), footer: VERTICES_TAG }
+    }
+}
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, serde::Serialize)]
+pub struct Faces {
+    pub header: u32,
+    #[br(assert(len <= MAX_SECTION_LEN, "faces section: absurd len {len} (max {MAX_SECTION_LEN})"))]
+    pub len: u32,
+    #[br(count = len as usize)]
+    pub faces: Vec<Face>,
+    #[br(assert(footer == header, "faces section: header/footer mismatch (header {header:#010x}, footer {footer:#010x})"))]
+    pub footer: u32,
+}
+
+impl Faces {
+    pub fn new() -> Faces {
+        Faces { header: FACES_TAG, len: 0, faces: vec![], footer: FACES_TAG }
+    }
+}
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, serde::Serialize)]
+pub struct Normals {
+    pub header: u32,
+    #[br(assert(len <= MAX_SECTION_LEN, "normals section: absurd len {len} (max {MAX_SECTION_LEN})"))]
+    pub len: u32,
+    #[br(count = len as usize)]
+    pub normals: Vec<Vertex>,
+    #[br(assert(footer == header, "normals section: header/footer mismatch (header {header:#010x}, footer {footer:#010x})"))]
+    pub footer: u32,
+}
+
+impl Normals {
+    pub fn new() -> Normals {
+        Normals { header: NORMALS_TAG, len: 0, normals: vec![], footer: NORMALS_TAG }
+    }
+}
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, serde::Serialize)]
+pub struct Group {
+    pub header: u32,
+    pub faces: Faces,
+    pub normals: Normals,
+    #[br(assert(footer == header, "group: header/footer mismatch (header {header:#010x}, footer {footer:#010x})"))]
+    pub footer: u32,
+}
+
+impl Group {
+    pub fn new() -> Group {
+        Group { header: GROUP_TAG, faces: Faces::new(), normals: Normals::new(), footer: GROUP_TAG }
+    }
+}
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, serde::Serialize)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct BMF {
+    pub header: u32,
+    pub vertices: Vertices,
+    pub group: Group,
+    #[br(assert(footer == header, "BMF: header/footer mismatch (header {header:#010x}, footer {footer:#010x})"))]
+    pub footer: u32,
+}
+
+impl BMF {
+    pub fn new() -> BMF {
+        BMF { header: BMF_TAG, vertices: Vertices::new(), group: Group::new(), footer: BMF_TAG }
+    }
+}
+
+/// Error parsing a BMF stream: either an I/O failure (including a stream that
+/// ends mid-section) or a parse failure. A binrw assertion failure (sentinel
+/// mismatch, absurd `len`) carries the byte offset at which parsing gave up;
+/// an out-of-range face index carries no offset.
+#[derive(Debug)]
+pub enum BmfError {
+    Io(std::io::Error),
+    Parse { offset: Option<u64>, message: String },
+}
+
+impl std::fmt::Display for BmfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BmfError::Io(e) => write!(f, "I/O error reading BMF stream: {e}"),
+            BmfError::Parse { offset: Some(offset), message } => {
+                write!(f, "BMF parse error at byte offset {offset}: {message}")
+            }
+            BmfError::Parse { offset: None, message } => write!(f, "BMF parse error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for BmfError {}
+
+impl From<binrw::Error> for BmfError {
+    fn from(err: binrw::Error) -> Self {
+        // Every nested struct (`Vertices`/`Faces`/`Group`/`BMF`) adds a context frame
+        // on failure, so the error binrw hands back is almost always an
+        // `Error::Backtrace` wrapping the real cause - unwrap to that before matching,
+        // or we lose the byte offset and print backtrace noise instead of the message.
+        match err.root_cause() {
+            binrw::Error::Io(e) => BmfError::Io(io_error_clone(e)),
+            binrw::Error::AssertFail { pos, message } => {
+                BmfError::Parse { offset: Some(*pos), message: message.clone() }
+            }
+            binrw::Error::BadMagic { pos, found } => {
+                BmfError::Parse { offset: Some(*pos), message: format!("unexpected magic value: {found:?}") }
+            }
+            other => BmfError::Parse { offset: None, message: other.to_string() },
+        }
+    }
+}
+
+/// `binrw::Error::Io` holds a non-`Clone` [`std::io::Error`], but `root_cause`
+/// only gives us a borrow of it - rebuild an equivalent owned error from its
+/// kind and message rather than consuming `err`.
+fn io_error_clone(e: &std::io::Error) -> std::io::Error {
+    std::io::Error::new(e.kind(), e.to_string())
+}
+
+/// Parses a BMF stream laid out as the fields of [`BMF`] and its nested
+/// section structs declare it: every section is a little-endian header u32,
+/// (for `Vertices`/`Faces`/`Normals`) a len u32 and `len` 12-byte elements,
+/// then a footer u32. `reader` need not be seekable itself (e.g. an HTTP response body), but
+/// `binrw` generates a rewind-on-failure (`restore_position`) around every
+/// nested struct here, and a rewind against a non-seekable stream just fails
+/// with its own I/O error, clobbering the real `AssertFail`/`BadMagic`/offset
+/// this module otherwise surfaces. So `reader` is read fully into memory
+/// first and parsed from a [`Cursor`], which can genuinely seek backwards.
+pub fn load_bmf<R: Read>(mut reader: R) -> Result<BMF, BmfError> {
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).map_err(BmfError::Io)?;
+    let mut cursor = Cursor::new(buf);
+    let bmf = BMF::read(&mut cursor).map_err(BmfError::from)?;
+    validate_face_indices(&bmf)?;
+    Ok(bmf)
+}
+
+/// Checks that every face's vertex indices are in bounds for `bmf.vertices`.
+/// `binrw`'s `assert`s catch a structurally corrupt stream, but a
+/// structurally valid one can still reference a vertex index past the end of
+/// the vertices section - callers like [`crate::normals`] index into the
+/// vertex slice with these without re-checking, so this must be caught here.
+fn validate_face_indices(bmf: &BMF) -> Result<(), BmfError> {
+    let vertex_count = bmf.vertices.vertices.len() as u64;
+    for (i, face) in bmf.group.faces.faces.iter().enumerate() {
+        for idx in [face.a, face.b, face.c] {
+            if idx as u64 >= vertex_count {
+                return Err(BmfError::Parse {
+                    offset: None,
+                    message: format!(
+                        "face {i} references vertex index {idx}, but only {vertex_count} vertices were read"
+                    ),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Serializes `bmf` back to the binary layout `load_bmf` expects: the same
+/// little-endian header/len/footer sentinels and vertex/face/normal data.
+/// `writer` need not be seekable, mirroring [`load_bmf`].
+pub fn write_bmf<W: Write>(bmf: &BMF, writer: W) -> Result<(), BmfError> {
+    let mut writer = NoSeek::new(writer);
+    bmf.write_le(&mut writer).map_err(BmfError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_face_indices_accepts_in_bounds_faces() {
+        let mut bmf = BMF::new();
+        bmf.vertices.vertices = vec![Vertex::default(), Vertex::default()];
+        bmf.group.faces.faces = vec![Face { a: 0, b: 1, c: 0 }];
+
+        assert!(validate_face_indices(&bmf).is_ok());
+    }
+
+    #[test]
+    fn validate_face_indices_rejects_out_of_bounds_face() {
+        let mut bmf = BMF::new();
+        bmf.vertices.vertices = vec![Vertex::default(), Vertex::default()];
+        bmf.group.faces.faces = vec![Face { a: 0, b: 1, c: 5 }];
+
+        let err = validate_face_indices(&bmf).unwrap_err();
+        assert!(matches!(err, BmfError::Parse { offset: None, .. }));
+    }
+
+    #[test]
+    fn from_binrw_error_unwraps_backtrace_to_root_cause() {
+        let cause = binrw::Error::AssertFail { pos: 42, message: "boom".to_string() };
+        let wrapped = binrw::Error::Backtrace(binrw::error::Backtrace::new(cause, vec![]));
+
+        let err: BmfError = wrapped.into();
+        match err {
+            BmfError::Parse { offset: Some(pos), message } => {
+                assert_eq!(pos, 42);
+                assert_eq!(message, "boom");
+            }
+            other => panic!("expected BmfError::Parse with offset, got {other:?}"),
+        }
+    }
+
+    /// Regression test for the real rewind-on-failure path (not the
+    /// hand-constructed `Error::Backtrace` in the test above): a genuinely
+    /// malformed stream, parsed through `load_bmf` end to end, must still
+    /// surface the real `AssertFail` offset/message rather than the I/O
+    /// error from binrw's failed rewind-seek.
+    #[test]
+    fn load_bmf_surfaces_assert_failure_not_a_rewind_seek_error() {
+        let mut bmf = BMF::new();
+        bmf.vertices.vertices = vec![Vertex::default()];
+        bmf.vertices.len = 1;
+
+        let mut bytes = Vec::new();
+        write_bmf(&bmf, &mut bytes).unwrap();
+
+        // flip a byte of the BMF's own trailing footer (the very last 4 bytes
+        // of the stream) so it no longer matches the header read at byte 0 -
+        // this guarantees the failing assert is far enough into the stream
+        // that binrw's rewind-on-failure has to seek backwards for real.
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+
+        let err = load_bmf(&bytes[..]).unwrap_err();
+        match err {
+            BmfError::Parse { offset: Some(_), message } => {
+                assert!(message.contains("header/footer mismatch"), "unexpected message: {message}");
+            }
+            other => panic!("expected BmfError::Parse with an offset, got {other:?}"),
+        }
+    }
+}
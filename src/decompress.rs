@@ -0,0 +1,87 @@
+use std::io::{Cursor, Read};
+
+use flate2::read::GzDecoder;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZIP_MAGIC: [u8; 4] = *b"PK\x03\x04";
+
+#[derive(Debug)]
+pub enum DecompressError {
+    Io(std::io::Error),
+    Zip(zip::result::ZipError),
+    EmptyArchive,
+}
+
+impl std::fmt::Display for DecompressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecompressError::Io(e) => write!(f, "I/O error sniffing input stream: {e}"),
+            DecompressError::Zip(e) => write!(f, "failed to read zip archive: {e}"),
+            DecompressError::EmptyArchive => write!(f, "zip archive contained no matching entry"),
+        }
+    }
+}
+
+impl std::error::Error for DecompressError {}
+
+impl From<std::io::Error> for DecompressError {
+    fn from(err: std::io::Error) -> Self {
+        DecompressError::Io(err)
+    }
+}
+
+impl From<zip::result::ZipError> for DecompressError {
+    fn from(err: zip::result::ZipError) -> Self {
+        DecompressError::Zip(err)
+    }
+}
+
+/// Sniffs the leading magic bytes of `reader` and transparently wraps it in a
+/// decompressor when appropriate: gzip/deflate (`1f 8b`-prefixed) is unwrapped
+/// with `flate2`, and a ZIP archive (`PK\x03\x04`-prefixed) has `archive_entry`
+/// (or its first entry, if unset) pulled out via the `zip` crate. Anything
+/// else is passed through unchanged.
+pub fn open_reader<R: Read + 'static>(
+    mut reader: R,
+    archive_entry: Option<&str>,
+) -> Result<Box<dyn Read>, DecompressError> {
+    let mut magic = [0u8; 4];
+    let mut read = 0;
+    while read < magic.len() {
+        let n = reader.read(&mut magic[read..])?;
+        if n == 0 {
+            break;
+        }
+        read += n;
+    }
+
+    let peeked = Cursor::new(magic[..read].to_vec()).chain(reader);
+
+    if read >= GZIP_MAGIC.len() && magic[..2] == GZIP_MAGIC {
+        return Ok(Box::new(GzDecoder::new(peeked)));
+    }
+
+    if read >= ZIP_MAGIC.len() && magic[..4] == ZIP_MAGIC {
+        return extract_zip_entry(peeked, archive_entry);
+    }
+
+    Ok(Box::new(peeked))
+}
+
+/// Streams through a ZIP archive one entry at a time (no `Seek` required) and
+/// buffers the first entry whose name matches `archive_entry`, or simply the
+/// first entry when `archive_entry` is `None`.
+fn extract_zip_entry<R: Read>(mut reader: R, archive_entry: Option<&str>) -> Result<Box<dyn Read>, DecompressError> {
+    loop {
+        match zip::read::read_zipfile_from_stream(&mut reader)? {
+            None => return Err(DecompressError::EmptyArchive),
+            Some(mut file) => {
+                if archive_entry.is_none_or(|name| file.name() == name) {
+                    let mut buf = Vec::new();
+                    file.read_to_end(&mut buf)?;
+                    return Ok(Box::new(Cursor::new(buf)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::fs::File;
+use std::io::{self, Write};
+
+use crate::bmf::{Face, Vertex};
+
+/// Output container for the glTF 2.0 export path.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GltfFormat {
+    /// `.gltf` - JSON document with the binary buffer embedded as a base64 data URI.
+    Text,
+    /// `.glb` - binary container with the JSON and buffer chunks concatenated.
+    Binary,
+}
+
+const GLB_MAGIC: u32 = 0x46546C67; // "glTF"
+const GLB_VERSION: u32 = 2;
+const GLB_CHUNK_TYPE_JSON: u32 = 0x4E4F534A; // "JSON"
+const GLB_CHUNK_TYPE_BIN: u32 = 0x004E4942; // "BIN\0"
+
+/// Packs `vertices`/`faces` into a single glTF 2.0 asset (one mesh, one primitive,
+/// `TRIANGLES` mode) and writes it to `path` as either a standalone `.gltf` document
+/// or a binary `.glb` container, per `format`.
+pub fn export_to_file(
+    vertices: &[Vertex],
+    faces: &[Face],
+    format: GltfFormat,
+    path: &str,
+) -> io::Result<()> {
+    let mut buffer = Vec::with_capacity(vertices.len() * 12 + faces.len() * 12);
+
+    // left at [0.0; 3] for an empty vertex list, since [f32::MAX; 3]/[f32::MIN; 3]
+    // would otherwise stand as the accessor's min/max with no vertex ever narrowing
+    // them - an inverted, spec-invalid range
+    let (mut min, mut max) = ([0f32; 3], [0f32; 3]);
+    for (n, v) in vertices.iter().enumerate() {
+        for (i, c) in [v.x, v.y, v.z].into_iter().enumerate() {
+            if n == 0 {
+                min[i] = c;
+                max[i] = c;
+            } else {
+                min[i] = min[i].min(c);
+                max[i] = max[i].max(c);
+            }
+        }
+        buffer.extend_from_slice(&v.x.to_le_bytes());
+        buffer.extend_from_slice(&v.y.to_le_bytes());
+        buffer.extend_from_slice(&v.z.to_le_bytes());
+    }
+    let position_bytes_len = buffer.len();
+
+    for f in faces {
+        buffer.extend_from_slice(&f.a.to_le_bytes());
+        buffer.extend_from_slice(&f.b.to_le_bytes());
+        buffer.extend_from_slice(&f.c.to_le_bytes());
+    }
+    let index_count = faces.len() * 3;
+
+    let json = build_gltf_json(
+        vertices.len(),
+        index_count,
+        position_bytes_len,
+        buffer.len() - position_bytes_len,
+        min,
+        max,
+        match format {
+            GltfFormat::Text => Some(&buffer),
+            GltfFormat::Binary => None,
+        },
+    );
+
+    let mut out = File::create(path)?;
+    match format {
+        GltfFormat::Text => out.write_all(json.as_bytes()),
+        GltfFormat::Binary => write_glb(&mut out, &json, &buffer),
+    }
+}
+
+/// Builds the glTF JSON document as a plain string. The buffer is either embedded
+/// as a base64 data URI (`embedded_buffer = Some(..)`, used for `.gltf`) or left
+/// URI-less so the binary chunk of a `.glb` container supplies it instead.
+fn build_gltf_json(
+    vertex_count: usize,
+    index_count: usize,
+    position_bytes_len: usize,
+    index_bytes_len: usize,
+    min: [f32; 3],
+    max: [f32; 3],
+    embedded_buffer: Option<&[u8]>,
+) -> String {
+    let buffer_byte_length = position_bytes_len + index_bytes_len;
+    let buffer_uri = match embedded_buffer {
+        Some(bytes) => format!(
+            r#","uri":"data:application/octet-stream;base64,{}""#,
+            base64_encode(bytes)
+        ),
+        None => String::new(),
+    };
+
+    format!(
+        r#"{{"asset":{{"version":"2.0","generator":"bmf2obj"}},"scene":0,"scenes":[{{"nodes":[0]}}],"nodes":[{{"mesh":0}}],"meshes":[{{"primitives":[{{"attributes":{{"POSITION":0}},"indices":1,"mode":4}}]}}],"buffers":[{{"byteLength":{buffer_byte_length}{buffer_uri}}}],"bufferViews":[{{"buffer":0,"byteOffset":0,"byteLength":{position_bytes_len},"target":34962}},{{"buffer":0,"byteOffset":{position_bytes_len},"byteLength":{index_bytes_len},"target":34963}}],"accessors":[{{"bufferView":0,"byteOffset":0,"componentType":5126,"count":{vertex_count},"type":"VEC3","min":[{min0},{min1},{min2}],"max":[{max0},{max1},{max2}]}},{{"bufferView":1,"byteOffset":0,"componentType":5125,"count":{index_count},"type":"SCALAR"}}]}}"#,
+        buffer_byte_length = buffer_byte_length,
+        buffer_uri = buffer_uri,
+        position_bytes_len = position_bytes_len,
+        index_bytes_len = index_bytes_len,
+        vertex_count = vertex_count,
+        index_count = index_count,
+        min0 = min[0],
+        min1 = min[1],
+        min2 = min[2],
+        max0 = max[0],
+        max1 = max[1],
+        max2 = max[2],
+    )
+}
+
+/// Writes the GLB container: a 12-byte header followed by a JSON chunk
+/// (space-padded to a 4-byte boundary) and a BIN chunk (zero-padded).
+fn write_glb<W: Write>(out: &mut W, json: &str, bin: &[u8]) -> io::Result<()> {
+    let json_padded_len = (json.len() + 3) & !3;
+    let bin_padded_len = (bin.len() + 3) & !3;
+    let total_len = 12 + (8 + json_padded_len) + (8 + bin_padded_len);
+
+    out.write_all(&GLB_MAGIC.to_le_bytes())?;
+    out.write_all(&GLB_VERSION.to_le_bytes())?;
+    out.write_all(&(total_len as u32).to_le_bytes())?;
+
+    out.write_all(&(json_padded_len as u32).to_le_bytes())?;
+    out.write_all(&GLB_CHUNK_TYPE_JSON.to_le_bytes())?;
+    out.write_all(json.as_bytes())?;
+    out.write_all(&vec![b' '; json_padded_len - json.len()])?;
+
+    out.write_all(&(bin_padded_len as u32).to_le_bytes())?;
+    out.write_all(&GLB_CHUNK_TYPE_BIN.to_le_bytes())?;
+    out.write_all(bin)?;
+    out.write_all(&vec![0u8; bin_padded_len - bin.len()])
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+
+        out.push(BASE64_ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(BASE64_ALPHABET[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize] as char);
+        out.push(if chunk.len() > 1 {
+            BASE64_ALPHABET[(((b1 & 0x0F) << 2) | (b2 >> 6)) as usize] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            BASE64_ALPHABET[(b2 & 0x3F) as usize] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
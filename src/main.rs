@@ -1,301 +1,215 @@
 use std::fs::File;
-use std::io::Read;
 
-use clap::{ArgGroup, Parser};
+use clap::{ArgGroup, Parser, ValueEnum};
 use obj_exporter::{export_to_file, Geometry, Object, ObjSet, Primitive, Shape, VertexIndex};
 
-#[derive(Debug, Copy, Clone)]
-struct Vertex {
-    x: f32,
-    y: f32,
-    z: f32,
-}
-
-#[derive(Debug, Copy, Clone)]
-struct Face {
-    a: u32,
-    b: u32,
-    c: u32,
-}
+use bmf::BMF;
+use gltf_exporter::GltfFormat;
 
-#[derive(Debug)]
-struct Vertices {
-    header: u32,
-    len: u32,
-    vertices: Vec<Vertex>,
-    footer: u32,
-}
+mod bmf;
+mod decompress;
+mod gltf_exporter;
+mod normals;
+mod obj_io;
 
-impl Vertices {
-    pub fn new() -> Vertices {
-        Vertices {
-            header: 0,
-            len: 0,
-            vertices: Vec::new(),
-            footer: 0,
-        }
-    }
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum OutputFormat {
+    Obj,
+    Gltf,
+    Glb,
 }
 
-#[derive(Debug)]
-struct Faces {
-    header: u32,
-    len: u32,
-    faces: Vec<Face>,
-    footer: u32,
-}
-
-impl Faces {
-    pub fn new() -> Faces {
-        Faces {
-            header: 0,
-            len: 0,
-            faces: vec![],
-            footer: 0,
-        }
-    }
-}
-
-#[derive(Debug)]
-struct Normals {
-    header: u32,
-    len: u32,
-    normals: Vec<Vertex>,
-    footer: u32,
-}
-
-impl Normals {
-    pub fn new() -> Normals {
-        Normals {
-            header: 0,
-            len: 0,
-            normals: vec![],
-            footer: 0,
-        }
-    }
-}
-
-#[derive(Debug)]
-struct Group {
-    header: u32,
-    faces: Faces,
-    normals: Normals,
-    footer: u32,
-}
-
-impl Group {
-    pub fn new() -> Group {
-        Group {
-            header: 0,
-            faces: Faces::new(),
-            normals: Normals::new(),
-            footer: 0,
-        }
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_possible_value().unwrap().get_name())
     }
 }
 
-#[derive(Debug)]
-struct BMF {
-    header: u32,
-    vertices: Vertices,
-    group: Group,
-    footer: u32,
-}
-
-impl BMF {
-    pub fn new() -> BMF {
-        BMF {
-            header: 0,
-            vertices: Vertices::new(),
-            group: Group::new(),
-            footer: 0,
-        }
-    }
-}
-
-fn as_vertex_le(array: &[u8; 12]) -> Vertex {
-    Vertex {
-        x: f32::from_le_bytes(<[u8; 4]>::try_from(&array[0..4]).unwrap()),
-        y: f32::from_le_bytes(<[u8; 4]>::try_from(&array[4..8]).unwrap()),
-        z: f32::from_le_bytes(<[u8; 4]>::try_from(&array[8..12]).unwrap()),
-    }
-}
-
-fn as_face_le(array: &[u8; 12]) -> Face {
-    Face {
-        a: u32::from_le_bytes(<[u8; 4]>::try_from(&array[0..4]).unwrap()),
-        b: u32::from_le_bytes(<[u8; 4]>::try_from(&array[4..8]).unwrap()),
-        c: u32::from_le_bytes(<[u8; 4]>::try_from(&array[8..12]).unwrap()),
-    }
-}
-
-/// Naive parsing of BMF format, which roughly is:
-///
-/// ```
-/// BMF Header
-///     Vertices Header u32
-///         Vertices Len u32
-///         Vertices Data (Len * 3 * f32)
-///     Vertices Footer u32
-///
-///     Group Header u32
-///         Faces Header u32
-///             Faces Len u32
-///             Faces Data (Len * 3 * u32)
-///         Faces Footer u32
-///
-///         Normals Header u32
-///             Normals Len u32
-///             Normals Data (Len * 3 * f32)
-///         Normals Footer u32
-///     Group Footer u32
-/// BMF Footer u32
-/// ```
-///
-/// There's potentially more advanced variations of these files, but the
-/// ones discovered so far only have this rigid format, as documented
-/// on http://paulbourke.net/dataformats/bmf_2/
-///
-/// # Arguments
-///
-/// * `reader`: reader implementing std::io::Read
-///
-/// returns: BMF
-///
-fn load_bmf<R>(reader: &mut R) -> Result<BMF, std::io::Error> where R: Read {
-    let mut bmf: BMF = BMF::new();
-    let mut buffer = [0; 4];
-
-    reader.read_exact(&mut buffer)?;
-    bmf.header = u32::from_le_bytes(<[u8; 4]>::try_from(buffer).unwrap());
-
-    reader.read_exact(&mut buffer)?;
-    bmf.vertices.header = u32::from_le_bytes(<[u8; 4]>::try_from(buffer).unwrap());
-
-    reader.read_exact(&mut buffer)?;
-    bmf.vertices.len = u32::from_le_bytes(<[u8; 4]>::try_from(buffer).unwrap());
-
-    for _i in 0..bmf.vertices.len {
-        let mut vert_buf = [0; 12];
-
-        reader.read_exact(&mut vert_buf)?;
-        let vert = as_vertex_le(&vert_buf);
-        bmf.vertices.vertices.push(vert);
-    }
-
-    reader.read_exact(&mut buffer)?;
-    bmf.vertices.footer = u32::from_le_bytes(<[u8; 4]>::try_from(buffer).unwrap());
-
-    reader.read_exact(&mut buffer)?;
-    bmf.group.header = u32::from_le_bytes(<[u8; 4]>::try_from(buffer).unwrap());
-
-    reader.read_exact(&mut buffer)?;
-    bmf.group.faces.header = u32::from_le_bytes(<[u8; 4]>::try_from(buffer).unwrap());
-
-    reader.read_exact(&mut buffer)?;
-    bmf.group.faces.len = u32::from_le_bytes(<[u8; 4]>::try_from(buffer).unwrap());
-
-
-    for _i in 0..bmf.group.faces.len {
-        let mut face_buf = [0; 12];
-
-        reader.read_exact(&mut face_buf)?;
-        let face = as_face_le(&face_buf);
-        bmf.group.faces.faces.push(face);
-    }
-
-    reader.read_exact(&mut buffer)?;
-    bmf.group.faces.footer = u32::from_le_bytes(<[u8; 4]>::try_from(buffer).unwrap());
-
-    reader.read_exact(&mut buffer)?;
-    bmf.group.normals.header = u32::from_le_bytes(<[u8; 4]>::try_from(buffer).unwrap());
-
-    reader.read_exact(&mut buffer)?;
-    bmf.group.normals.len = u32::from_le_bytes(<[u8; 4]>::try_from(buffer).unwrap());
-
-    for _i in 0..bmf.group.normals.len {
-        let mut norm_buf = [0; 12];
-
-        reader.read_exact(&mut norm_buf)?;
-        let norm = as_vertex_le(&norm_buf);
-        bmf.group.normals.normals.push(norm);
-    }
-
-    reader.read_exact(&mut buffer)?;
-    bmf.group.normals.footer = u32::from_le_bytes(<[u8; 4]>::try_from(buffer).unwrap());
-
-    reader.read_exact(&mut buffer)?;
-    bmf.group.footer = u32::from_le_bytes(<[u8; 4]>::try_from(buffer).unwrap());
-
-    reader.read_exact(&mut buffer)?;
-    bmf.footer = u32::from_le_bytes(<[u8; 4]>::try_from(buffer).unwrap());
-
-    return Ok(bmf);
+/// Which direction the conversion runs, independent of `--format`: `Bmf` reads
+/// an OBJ source and writes a BMF file (the inverse of the default path),
+/// `Obj` is the default BMF-reading behaviour.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum ConvertTo {
+    Bmf,
+    Obj,
 }
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 #[command(group(ArgGroup::new("source").required(true).args(["file", "url"])))]
+#[command(group(ArgGroup::new("destination").multiple(true).required(true).args(["out", "dump_json"])))]
 struct Cli {
     #[arg(long)]
     file: Option<String>,
     #[arg(long)]
     url: Option<String>,
     #[arg(long)]
-    out: String,
+    out: Option<String>,
+    #[arg(long, value_enum, default_value_t = OutputFormat::Obj)]
+    format: OutputFormat,
+    /// Conversion direction; inferred from the `--file` extension (`.obj` =>
+    /// `bmf`) when omitted, which is the only way to select it for `--url`.
+    #[arg(long, value_enum)]
+    to: Option<ConvertTo>,
+    /// Name of the file to pull out of a ZIP-wrapped `--file`/`--url` source.
+    /// Defaults to the archive's first entry.
+    #[arg(long)]
+    archive_entry: Option<String>,
+    /// Dump the BMF structure (including section header/footer/len sentinels)
+    /// to this path as JSON, for inspecting unknown BMF variants. Works for
+    /// both directions: the BMF parsed from `--file`/`--url`, or the BMF built
+    /// from an OBJ source when `--to bmf`. `--out` becomes optional when this
+    /// is given.
+    #[arg(long)]
+    dump_json: Option<String>,
 }
 
 fn main() {
     let cli: Cli = Cli::parse();
 
+    let to_bmf = match cli.to {
+        Some(to) => to == ConvertTo::Bmf,
+        None => cli.file.as_deref().is_some_and(|f| f.to_lowercase().ends_with(".obj")),
+    };
+
+    if to_bmf {
+        return convert_obj_to_bmf(&cli);
+    }
+
     // match method to pull source data to create bmf
-    let bmf: BMF = match (cli.file, cli.url) {
+    let reader = match (cli.file, cli.url) {
         (None, Some(url)) => {
-            let mut body = reqwest::blocking::get(url).expect("Failed to open url");
-            load_bmf(&mut body).expect("Failed to load BMF")
+            let body = reqwest::blocking::get(url).expect("Failed to open url");
+            decompress::open_reader(body, cli.archive_entry.as_deref())
         }
         (Some(file), None) => {
-            let mut file = File::open(file).expect("Failed to open file");
-            load_bmf(&mut file).expect("Failed to load BMF")
+            let file = File::open(file).expect("Failed to open file");
+            decompress::open_reader(file, cli.archive_entry.as_deref())
         }
         (_, _) => unreachable!()
-    };
+    }.unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
 
-    // convert from BMF vertices to OBJ vertices (just a cast from u32 to u64)
-    let obj_verts: Vec<obj_exporter::Vertex> = bmf.vertices.vertices.iter().
-        map(|v| obj_exporter::Vertex {
-            x: v.x as f64,
-            y: v.y as f64,
-            z: v.z as f64,
-        }).collect();
+    let parsed = bmf::load_bmf(reader);
 
-    // build up geometry for OBJ from BMF faces, don't include normals or texture
-    // since we don't have texture and normals need to be recomputed anyways
-    let obj_shapes: Vec<Shape> = bmf.group.faces.faces.iter().
-        map(|&f| Shape {
-            primitive: Primitive::Triangle(
-                (f.a as VertexIndex, None, None),
-                (f.b as VertexIndex, None, None),
-                (f.c as VertexIndex, None, None),
-            ),
-            groups: vec![],
-            smoothing_groups: vec![],
-        }).collect();
+    let bmf: BMF = parsed.unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
 
-    // build the object and object set (only one of each) to export
-    let object_set = ObjSet {
-        material_library: None,
-        objects: vec![Object {
-            name: "".to_string(),
-            vertices: obj_verts,
-            tex_vertices: vec![],
-            normals: vec![],
-            geometry: vec![Geometry {
-                material_name: None,
-                shapes: obj_shapes,
-            }],
-        }],
+    if let Some(dump_path) = &cli.dump_json {
+        let json = serde_json::to_string_pretty(&bmf).expect("failed to serialize BMF to JSON");
+        std::fs::write(dump_path, json).expect("failed to write JSON dump");
+    }
+
+    let out = match cli.out {
+        Some(out) => out,
+        None => return,
     };
 
-    export_to_file(&object_set, cli.out).expect("failed to export obj");
+    match cli.format {
+        OutputFormat::Obj => {
+            // convert from BMF vertices to OBJ vertices (just a cast from f32 to f64)
+            let obj_verts: Vec<obj_exporter::Vertex> = bmf.vertices.vertices.iter().
+                map(|v| obj_exporter::Vertex {
+                    x: v.x as f64,
+                    y: v.y as f64,
+                    z: v.z as f64,
+                }).collect();
+
+            // one normal per vertex, either taken from the BMF file or recomputed
+            let obj_normals = normals::resolve_normals(
+                &bmf.vertices.vertices,
+                &bmf.group.faces.faces,
+                &bmf.group.normals.normals,
+            );
+
+            // build up geometry for OBJ from BMF faces, referencing the matching
+            // per-vertex normal (texture coordinates remain unsupported)
+            let obj_shapes: Vec<Shape> = bmf.group.faces.faces.iter().
+                map(|&f| Shape {
+                    primitive: Primitive::Triangle(
+                        (f.a as VertexIndex, None, Some(f.a as VertexIndex)),
+                        (f.b as VertexIndex, None, Some(f.b as VertexIndex)),
+                        (f.c as VertexIndex, None, Some(f.c as VertexIndex)),
+                    ),
+                    groups: vec![],
+                    smoothing_groups: vec![],
+                }).collect();
+
+            // build the object and object set (only one of each) to export
+            let object_set = ObjSet {
+                material_library: None,
+                objects: vec![Object {
+                    name: "".to_string(),
+                    vertices: obj_verts,
+                    tex_vertices: vec![],
+                    normals: obj_normals,
+                    geometry: vec![Geometry {
+                        material_name: None,
+                        shapes: obj_shapes,
+                    }],
+                }],
+            };
+
+            export_to_file(&object_set, out).expect("failed to export obj");
+        }
+        OutputFormat::Gltf | OutputFormat::Glb => {
+            let format = match cli.format {
+                OutputFormat::Gltf => GltfFormat::Text,
+                OutputFormat::Glb => GltfFormat::Binary,
+                OutputFormat::Obj => unreachable!(),
+            };
+
+            gltf_exporter::export_to_file(
+                &bmf.vertices.vertices,
+                &bmf.group.faces.faces,
+                format,
+                &out,
+            ).expect("failed to export gltf");
+        }
+    }
+}
+
+/// Reverse path: reads a Wavefront OBJ source and writes it back out as a
+/// BMF file, making the tool a round-trip converter.
+fn convert_obj_to_bmf(cli: &Cli) {
+    let reader = match (&cli.file, &cli.url) {
+        (None, Some(url)) => {
+            let body = reqwest::blocking::get(url).expect("Failed to open url");
+            decompress::open_reader(body, cli.archive_entry.as_deref())
+        }
+        (Some(file), None) => {
+            let file = File::open(file).expect("Failed to open file");
+            decompress::open_reader(file, cli.archive_entry.as_deref())
+        }
+        (_, _) => unreachable!()
+    }.unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
+
+    let parsed = obj_io::load_obj(reader);
+
+    let bmf: BMF = parsed.unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
+
+    if let Some(dump_path) = &cli.dump_json {
+        let json = serde_json::to_string_pretty(&bmf).expect("failed to serialize BMF to JSON");
+        std::fs::write(dump_path, json).expect("failed to write JSON dump");
+    }
+
+    let out_path = match &cli.out {
+        Some(out_path) => out_path,
+        None => return,
+    };
+    let out = File::create(out_path).expect("failed to create output file");
+    bmf::write_bmf(&bmf, out).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
 }
\ No newline at end of file
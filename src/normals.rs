@@ -0,0 +1,124 @@
+use obj_exporter::Vertex as Normal;
+
+use crate::bmf::{Face, Vertex};
+
+/// Below this squared length a face's cross product is treated as degenerate
+/// (zero-area triangle) and skipped when accumulating normals.
+const DEGENERATE_EPSILON: f32 = 1e-12;
+
+/// Resolves the per-vertex normals to export alongside `vertices`/`faces`.
+///
+/// If `bmf_normals` holds one usable (non-zero) entry per vertex, those are used
+/// as-is. Otherwise smooth per-vertex normals are recomputed from face geometry.
+pub fn resolve_normals(vertices: &[Vertex], faces: &[Face], bmf_normals: &[Vertex]) -> Vec<Normal> {
+    if bmf_normals.len() == vertices.len() && bmf_normals.iter().all(|n| !is_zero(n)) {
+        return bmf_normals.iter().map(|n| Normal { x: n.x as f64, y: n.y as f64, z: n.z as f64 }).collect();
+    }
+
+    recompute_smooth_normals(vertices, faces)
+}
+
+fn is_zero(v: &Vertex) -> bool {
+    v.x == 0.0 && v.y == 0.0 && v.z == 0.0
+}
+
+/// Area-weighted smooth normal recompute: each face contributes its (un-normalized)
+/// cross-product normal to the accumulators of its three vertices, then every
+/// accumulator is normalized. Vertices untouched by any non-degenerate face fall
+/// back to `(0, 0, 1)`.
+fn recompute_smooth_normals(vertices: &[Vertex], faces: &[Face]) -> Vec<Normal> {
+    let mut accum = vec![[0f32; 3]; vertices.len()];
+
+    for face in faces {
+        let a = vertices[face.a as usize];
+        let b = vertices[face.b as usize];
+        let c = vertices[face.c as usize];
+
+        let ab = [b.x - a.x, b.y - a.y, b.z - a.z];
+        let ac = [c.x - a.x, c.y - a.y, c.z - a.z];
+        let cross = [
+            ab[1] * ac[2] - ab[2] * ac[1],
+            ab[2] * ac[0] - ab[0] * ac[2],
+            ab[0] * ac[1] - ab[1] * ac[0],
+        ];
+
+        let len_sq = cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2];
+        if len_sq < DEGENERATE_EPSILON {
+            continue;
+        }
+
+        for &idx in &[face.a, face.b, face.c] {
+            let acc = &mut accum[idx as usize];
+            acc[0] += cross[0];
+            acc[1] += cross[1];
+            acc[2] += cross[2];
+        }
+    }
+
+    accum.into_iter().map(|n| {
+        let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+        if len < f32::EPSILON {
+            Normal { x: 0.0, y: 0.0, z: 1.0 }
+        } else {
+            Normal { x: (n[0] / len) as f64, y: (n[1] / len) as f64, z: (n[2] / len) as f64 }
+        }
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> (Vec<Vertex>, Vec<Face>) {
+        let vertices = vec![
+            Vertex { x: 0.0, y: 0.0, z: 0.0 },
+            Vertex { x: 1.0, y: 0.0, z: 0.0 },
+            Vertex { x: 0.0, y: 1.0, z: 0.0 },
+        ];
+        let faces = vec![Face { a: 0, b: 1, c: 2 }];
+        (vertices, faces)
+    }
+
+    #[test]
+    fn resolve_normals_uses_bmf_normals_when_present_and_non_zero() {
+        let (vertices, faces) = triangle();
+        let bmf_normals = vec![
+            Vertex { x: 0.0, y: 0.0, z: 1.0 },
+            Vertex { x: 0.0, y: 0.0, z: 1.0 },
+            Vertex { x: 0.0, y: 0.0, z: 1.0 },
+        ];
+
+        let normals = resolve_normals(&vertices, &faces, &bmf_normals);
+
+        assert_eq!(normals.len(), 3);
+        assert_eq!((normals[0].x, normals[0].y, normals[0].z), (0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn resolve_normals_recomputes_when_bmf_normals_absent() {
+        let (vertices, faces) = triangle();
+
+        let normals = resolve_normals(&vertices, &faces, &[]);
+
+        assert_eq!(normals.len(), 3);
+        // the triangle lies flat in the xy-plane, so every vertex normal should point +z
+        for normal in normals {
+            assert!((normal.z - 1.0).abs() < 1e-6, "expected +z normal, got {normal:?}");
+        }
+    }
+
+    #[test]
+    fn recompute_smooth_normals_falls_back_to_up_for_unreferenced_vertex() {
+        let vertices = vec![
+            Vertex { x: 0.0, y: 0.0, z: 0.0 },
+            Vertex { x: 1.0, y: 0.0, z: 0.0 },
+            Vertex { x: 0.0, y: 1.0, z: 0.0 },
+        ];
+        // only vertices 0 and 1 are referenced by any face
+        let faces = vec![Face { a: 0, b: 1, c: 0 }];
+
+        let normals = recompute_smooth_normals(&vertices, &faces);
+
+        assert_eq!((normals[2].x, normals[2].y, normals[2].z), (0.0, 0.0, 1.0));
+    }
+}
@@ -0,0 +1,191 @@
+use std::io::{BufRead, BufReader, Read};
+
+use crate::bmf::{Face, Vertex, BMF};
+
+/// Error parsing a Wavefront OBJ stream: an I/O failure, or a parse failure
+/// tagged with the `.obj` line number that triggered it.
+#[derive(Debug)]
+pub enum ObjError {
+    Io(std::io::Error),
+    Parse { line: usize, message: String },
+}
+
+impl std::fmt::Display for ObjError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjError::Io(e) => write!(f, "I/O error reading OBJ stream: {e}"),
+            ObjError::Parse { line, message } => write!(f, "OBJ parse error on line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ObjError {}
+
+impl From<std::io::Error> for ObjError {
+    fn from(err: std::io::Error) -> Self {
+        ObjError::Io(err)
+    }
+}
+
+/// Parses a Wavefront `.obj` stream into a [`BMF`], the inverse of the OBJ
+/// export path. `v` lines become vertices, `vn` lines become the normals
+/// section verbatim (resolved/recomputed downstream if their count doesn't
+/// match the vertex count), and `f` lines become triangle faces: polygonal
+/// faces are fan-triangulated and negative (relative) indices are resolved
+/// against the vertex count seen so far, per the OBJ spec.
+pub fn load_obj<R: Read>(reader: R) -> Result<BMF, ObjError> {
+    let mut bmf = BMF::new();
+
+    for (line_no, line) in BufReader::new(reader).lines().enumerate() {
+        let line_no = line_no + 1;
+        let line = line?;
+        let line = line.trim();
+
+        let mut tokens = line.split_whitespace();
+        let keyword = match tokens.next() {
+            Some(k) => k,
+            None => continue,
+        };
+
+        match keyword {
+            "v" => {
+                let v = parse_vertex(&mut tokens, line_no)?;
+                bmf.vertices.vertices.push(v);
+            }
+            "vn" => {
+                let n = parse_vertex(&mut tokens, line_no)?;
+                bmf.group.normals.normals.push(n);
+            }
+            "f" => {
+                let vertex_count = bmf.vertices.vertices.len();
+                let indices: Vec<u32> = tokens
+                    .map(|tok| parse_face_index(tok, vertex_count, line_no))
+                    .collect::<Result<_, _>>()?;
+
+                if indices.len() < 3 {
+                    return Err(ObjError::Parse {
+                        line: line_no,
+                        message: format!("face needs at least 3 vertices, found {}", indices.len()),
+                    });
+                }
+
+                // fan-triangulate any polygon beyond a plain triangle
+                for i in 1..indices.len() - 1 {
+                    bmf.group.faces.faces.push(Face {
+                        a: indices[0],
+                        b: indices[i],
+                        c: indices[i + 1],
+                    });
+                }
+            }
+            _ => {} // comments, mtllib/usemtl, vt, etc. are irrelevant to the BMF geometry
+        }
+    }
+
+    bmf.vertices.len = bmf.vertices.vertices.len() as u32;
+    bmf.group.faces.len = bmf.group.faces.faces.len() as u32;
+    bmf.group.normals.len = bmf.group.normals.normals.len() as u32;
+
+    Ok(bmf)
+}
+
+fn parse_vertex<'a>(tokens: &mut impl Iterator<Item = &'a str>, line_no: usize) -> Result<Vertex, ObjError> {
+    let mut parse_next = || -> Result<f32, ObjError> {
+        tokens
+            .next()
+            .ok_or_else(|| ObjError::Parse { line: line_no, message: "expected 3 components".to_string() })?
+            .parse::<f32>()
+            .map_err(|e| ObjError::Parse { line: line_no, message: format!("invalid float: {e}") })
+    };
+
+    Ok(Vertex { x: parse_next()?, y: parse_next()?, z: parse_next()? })
+}
+
+/// Parses one `f` line token (`v`, `v/vt`, `v//vn` or `v/vt/vn`) down to its
+/// vertex index, resolving a negative (relative-to-end) index per the OBJ
+/// spec and converting from 1-based to 0-based.
+fn parse_face_index(token: &str, vertex_count: usize, line_no: usize) -> Result<u32, ObjError> {
+    let raw = token.split('/').next().unwrap_or(token);
+    let index: i64 = raw
+        .parse()
+        .map_err(|e| ObjError::Parse { line: line_no, message: format!("invalid face index {raw:?}: {e}") })?;
+
+    let resolved = if index < 0 {
+        vertex_count as i64 + index
+    } else {
+        index - 1
+    };
+
+    if resolved < 0 || resolved >= vertex_count as i64 {
+        return Err(ObjError::Parse {
+            line: line_no,
+            message: format!("face index {index} out of range for {vertex_count} vertices"),
+        });
+    }
+
+    Ok(resolved as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_obj_parses_vertices_normals_and_a_triangle() {
+        let obj = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nf 1 2 3\n";
+
+        let bmf = load_obj(obj.as_bytes()).unwrap();
+
+        assert_eq!(bmf.vertices.vertices.len(), 3);
+        assert_eq!(bmf.group.normals.normals.len(), 1);
+        assert_eq!(bmf.group.faces.faces.len(), 1);
+        let face = bmf.group.faces.faces[0];
+        assert_eq!((face.a, face.b, face.c), (0, 1, 2));
+    }
+
+    #[test]
+    fn load_obj_fan_triangulates_polygons() {
+        let obj = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3 4\n";
+
+        let bmf = load_obj(obj.as_bytes()).unwrap();
+
+        // a quad fan-triangulates into 2 triangles sharing the first vertex
+        assert_eq!(bmf.group.faces.faces.len(), 2);
+        assert_eq!((bmf.group.faces.faces[0].a, bmf.group.faces.faces[0].b, bmf.group.faces.faces[0].c), (0, 1, 2));
+        assert_eq!((bmf.group.faces.faces[1].a, bmf.group.faces.faces[1].b, bmf.group.faces.faces[1].c), (0, 2, 3));
+    }
+
+    #[test]
+    fn load_obj_resolves_negative_relative_indices() {
+        let obj = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf -3 -2 -1\n";
+
+        let bmf = load_obj(obj.as_bytes()).unwrap();
+
+        assert_eq!((bmf.group.faces.faces[0].a, bmf.group.faces.faces[0].b, bmf.group.faces.faces[0].c), (0, 1, 2));
+    }
+
+    #[test]
+    fn load_obj_accepts_v_vt_vn_face_tokens() {
+        let obj = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1/1/1 2/2/1 3/3/1\n";
+
+        let bmf = load_obj(obj.as_bytes()).unwrap();
+
+        assert_eq!(bmf.group.faces.faces.len(), 1);
+    }
+
+    #[test]
+    fn load_obj_rejects_out_of_range_face_index() {
+        let obj = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 4\n";
+
+        let err = load_obj(obj.as_bytes()).unwrap_err();
+        assert!(matches!(err, ObjError::Parse { line: 4, .. }));
+    }
+
+    #[test]
+    fn load_obj_rejects_degenerate_face_with_fewer_than_3_vertices() {
+        let obj = "v 0 0 0\nv 1 0 0\nf 1 2\n";
+
+        let err = load_obj(obj.as_bytes()).unwrap_err();
+        assert!(matches!(err, ObjError::Parse { line: 3, .. }));
+    }
+}